@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use git2::{Repository, Status, StatusOptions};
+
+/// A file's Git working-tree state, collapsed to the handful of states
+/// worth a column in the listing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitStatus {
+    Untracked,
+    Modified,
+    Staged,
+    Ignored,
+    Clean,
+}
+
+impl GitStatus {
+    /// Short colored flag, the way `exa`/`lsd` render a git status column.
+    pub fn colored_flag(self) -> &'static str {
+        match self {
+            GitStatus::Untracked => "\x1b[31m??\x1b[0m",
+            GitStatus::Modified => "\x1b[33mM \x1b[0m",
+            GitStatus::Staged => "\x1b[32mA \x1b[0m",
+            GitStatus::Ignored => "\x1b[90m!!\x1b[0m",
+            GitStatus::Clean => "  ",
+        }
+    }
+
+    /// Same flag without ANSI codes, for `--color never` or non-tty output.
+    pub fn flag(self) -> &'static str {
+        match self {
+            GitStatus::Untracked => "??",
+            GitStatus::Modified => "M ",
+            GitStatus::Staged => "A ",
+            GitStatus::Ignored => "!!",
+            GitStatus::Clean => "  ",
+        }
+    }
+}
+
+/// Git status for every file in a repository, computed once up front so
+/// `list_dir` doesn't open the repo or re-walk the index per file.
+pub struct GitStatuses {
+    workdir: PathBuf,
+    statuses: HashMap<PathBuf, GitStatus>,
+}
+
+impl GitStatuses {
+    /// Opens the repository containing `dir` and reads every path's status.
+    /// Returns `None` when `dir` isn't inside a Git repository, so callers
+    /// can skip the column gracefully instead of erroring.
+    pub fn discover(dir: &Path) -> Option<Self> {
+        let repo = Repository::discover(dir).ok()?;
+        let workdir = repo.workdir()?.canonicalize().ok()?;
+
+        let mut opts = StatusOptions::new();
+        opts.include_untracked(true)
+            .include_ignored(true)
+            .recurse_untracked_dirs(true)
+            .recurse_ignored_dirs(true);
+        let statuses = repo.statuses(Some(&mut opts)).ok()?;
+
+        let mut by_path = HashMap::new();
+        for entry in statuses.iter() {
+            let Some(path) = entry.path() else {
+                continue;
+            };
+            by_path.insert(PathBuf::from(path), classify(entry.status()));
+        }
+
+        Some(Self {
+            workdir,
+            statuses: by_path,
+        })
+    }
+
+    /// Looks up the status for a file path, matching against the
+    /// repo-relative paths Git reported.
+    ///
+    /// Only the parent directory is canonicalized, not the entry itself: if
+    /// `path` is a symlink, canonicalizing the whole thing would resolve to
+    /// its target and we'd report the target's status instead of the link's
+    /// own (e.g. an untracked symlink to a clean file would show as clean).
+    pub fn status_for(&self, path: &Path) -> GitStatus {
+        let Some(file_name) = path.file_name() else {
+            return GitStatus::Clean;
+        };
+        let parent = path.parent().unwrap_or_else(|| Path::new("."));
+        let canonical_parent = match parent.canonicalize() {
+            Ok(p) => p,
+            Err(_) => return GitStatus::Clean,
+        };
+        let canonical = canonical_parent.join(file_name);
+        let relative = match canonical.strip_prefix(&self.workdir) {
+            Ok(p) => p,
+            Err(_) => return GitStatus::Clean,
+        };
+        self.statuses
+            .get(relative)
+            .copied()
+            .unwrap_or(GitStatus::Clean)
+    }
+
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        self.status_for(path) == GitStatus::Ignored
+    }
+}
+
+fn classify(status: Status) -> GitStatus {
+    if status.contains(Status::IGNORED) {
+        GitStatus::Ignored
+    } else if status.contains(Status::WT_NEW) {
+        GitStatus::Untracked
+    } else if status.intersects(
+        Status::INDEX_NEW
+            | Status::INDEX_MODIFIED
+            | Status::INDEX_DELETED
+            | Status::INDEX_RENAMED
+            | Status::INDEX_TYPECHANGE,
+    ) {
+        GitStatus::Staged
+    } else if status.intersects(
+        Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_RENAMED | Status::WT_TYPECHANGE,
+    ) {
+        GitStatus::Modified
+    } else {
+        GitStatus::Clean
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignored_wins_over_everything_else() {
+        assert_eq!(
+            classify(Status::IGNORED | Status::WT_MODIFIED),
+            GitStatus::Ignored
+        );
+    }
+
+    #[test]
+    fn untracked_files_are_not_staged() {
+        assert_eq!(classify(Status::WT_NEW), GitStatus::Untracked);
+    }
+
+    #[test]
+    fn any_index_change_counts_as_staged() {
+        assert_eq!(classify(Status::INDEX_NEW), GitStatus::Staged);
+        assert_eq!(classify(Status::INDEX_MODIFIED), GitStatus::Staged);
+        assert_eq!(classify(Status::INDEX_DELETED), GitStatus::Staged);
+        assert_eq!(classify(Status::INDEX_RENAMED), GitStatus::Staged);
+        assert_eq!(classify(Status::INDEX_TYPECHANGE), GitStatus::Staged);
+    }
+
+    #[test]
+    fn staged_takes_priority_over_worktree_modifications() {
+        assert_eq!(
+            classify(Status::INDEX_MODIFIED | Status::WT_MODIFIED),
+            GitStatus::Staged
+        );
+    }
+
+    #[test]
+    fn any_worktree_change_counts_as_modified() {
+        assert_eq!(classify(Status::WT_MODIFIED), GitStatus::Modified);
+        assert_eq!(classify(Status::WT_DELETED), GitStatus::Modified);
+        assert_eq!(classify(Status::WT_RENAMED), GitStatus::Modified);
+        assert_eq!(classify(Status::WT_TYPECHANGE), GitStatus::Modified);
+    }
+
+    #[test]
+    fn no_flags_means_clean() {
+        assert_eq!(classify(Status::CURRENT), GitStatus::Clean);
+    }
+
+    /// Builds a throwaway repo under the system temp dir with a tracked
+    /// clean file, a gitignored file, and symlinks pointing at each, then
+    /// returns its `GitStatuses` plus the repo's root for path-joining.
+    fn repo_with_symlinks() -> (GitStatuses, PathBuf) {
+        let root = std::env::temp_dir().join(format!(
+            "recent-git-status-test-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        std::fs::create_dir_all(&root).unwrap();
+
+        let repo = Repository::init(&root).unwrap();
+        std::fs::write(root.join("file1.txt"), "clean\n").unwrap();
+        std::fs::write(root.join(".gitignore"), "bigfile\n").unwrap();
+        std::fs::write(root.join("bigfile"), "ignored\n").unwrap();
+        std::os::unix::fs::symlink("file1.txt", root.join("link_to_file")).unwrap();
+        std::os::unix::fs::symlink("bigfile", root.join("link_to_ignored")).unwrap();
+
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("file1.txt")).unwrap();
+        index.add_path(Path::new(".gitignore")).unwrap();
+        index.write().unwrap();
+        let tree_id = index.write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+            .unwrap();
+
+        let statuses = GitStatuses::discover(&root).unwrap();
+        (statuses, root)
+    }
+
+    #[test]
+    fn status_for_reports_the_symlink_itself_not_its_target() {
+        let (statuses, root) = repo_with_symlinks();
+        assert_eq!(
+            statuses.status_for(&root.join("file1.txt")),
+            GitStatus::Clean
+        );
+        assert_eq!(
+            statuses.status_for(&root.join("link_to_file")),
+            GitStatus::Untracked
+        );
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn is_ignored_does_not_follow_a_symlink_into_an_ignored_target() {
+        let (statuses, root) = repo_with_symlinks();
+        assert!(statuses.is_ignored(&root.join("bigfile")));
+        assert!(!statuses.is_ignored(&root.join("link_to_ignored")));
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::env;
+use std::path::Path;
+
+/// The handful of file "kinds" `LS_COLORS` assigns dedicated type codes to.
+/// Anything else falls back to matching the filename's extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    Directory,
+    Symlink,
+    Executable,
+    Regular,
+}
+
+/// A parsed `LS_COLORS`/`dircolors` style sheet: ANSI SGR codes keyed by
+/// file-type code (`di`, `ln`, `ex`, ...) or by lowercased extension
+/// (`*.rs` -> `rs`).
+pub struct LsColors {
+    by_type: HashMap<String, String>,
+    by_extension: HashMap<String, String>,
+}
+
+impl LsColors {
+    /// Reads and parses the `LS_COLORS` environment variable, or an empty
+    /// (no-color) sheet when it isn't set.
+    pub fn from_env() -> Self {
+        Self::parse(&env::var("LS_COLORS").unwrap_or_default())
+    }
+
+    /// Parses a `di=01;34:ln=01;36:*.rs=01;33` style spec.
+    pub fn parse(spec: &str) -> Self {
+        let mut by_type = HashMap::new();
+        let mut by_extension = HashMap::new();
+
+        for entry in spec.split(':').filter(|s| !s.is_empty()) {
+            let Some((key, value)) = entry.split_once('=') else {
+                continue;
+            };
+            if let Some(ext) = key.strip_prefix("*.") {
+                by_extension.insert(ext.to_ascii_lowercase(), value.to_string());
+            } else {
+                by_type.insert(key.to_string(), value.to_string());
+            }
+        }
+
+        Self {
+            by_type,
+            by_extension,
+        }
+    }
+
+    /// Picks the SGR code for a file: type first (directory, symlink,
+    /// executable), then the filename's extension, then the default file
+    /// style if one is set.
+    pub fn style_for(&self, kind: FileKind, name: &str) -> Option<&str> {
+        match kind {
+            FileKind::Directory => self.by_type.get("di"),
+            FileKind::Symlink => self.by_type.get("ln"),
+            FileKind::Executable => self.by_type.get("ex"),
+            FileKind::Regular => Path::new(name)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .and_then(|ext| self.by_extension.get(&ext.to_ascii_lowercase()))
+                .or_else(|| self.by_type.get("fi")),
+        }
+        .map(|s| s.as_str())
+    }
+
+    /// Wraps `text` in the style's ANSI escape codes, or returns it
+    /// unstyled when there's no match.
+    pub fn paint(&self, kind: FileKind, name: &str, text: &str) -> String {
+        match self.style_for(kind, name) {
+            Some(style) => format!("\x1b[{}m{}\x1b[0m", style, text),
+            None => text.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn type_codes_take_priority_over_extension() {
+        let colors = LsColors::parse("di=01;34:ln=01;36:ex=01;32:*.rs=01;33");
+        assert_eq!(colors.style_for(FileKind::Directory, "src"), Some("01;34"));
+        assert_eq!(colors.style_for(FileKind::Symlink, "link.rs"), Some("01;36"));
+        assert_eq!(colors.style_for(FileKind::Executable, "a.out"), Some("01;32"));
+    }
+
+    #[test]
+    fn regular_files_fall_back_to_extension() {
+        let colors = LsColors::parse("di=01;34:*.rs=01;33");
+        assert_eq!(colors.style_for(FileKind::Regular, "main.rs"), Some("01;33"));
+        assert_eq!(colors.style_for(FileKind::Regular, "README"), None);
+    }
+
+    #[test]
+    fn extension_matching_is_case_insensitive() {
+        let colors = LsColors::parse("*.RS=01;33");
+        assert_eq!(colors.style_for(FileKind::Regular, "main.rs"), Some("01;33"));
+    }
+
+    #[test]
+    fn unmatched_regular_file_uses_default_style() {
+        let colors = LsColors::parse("fi=00");
+        assert_eq!(colors.style_for(FileKind::Regular, "README"), Some("00"));
+    }
+
+    #[test]
+    fn empty_spec_has_no_styles() {
+        let colors = LsColors::parse("");
+        assert_eq!(colors.style_for(FileKind::Directory, "src"), None);
+        assert_eq!(colors.style_for(FileKind::Regular, "main.rs"), None);
+    }
+
+    #[test]
+    fn paint_wraps_in_ansi_codes_or_passes_through() {
+        let colors = LsColors::parse("di=01;34");
+        assert_eq!(
+            colors.paint(FileKind::Directory, "src", "src"),
+            "\x1b[01;34msrc\x1b[0m"
+        );
+        assert_eq!(colors.paint(FileKind::Regular, "README", "README"), "README");
+    }
+}
@@ -4,6 +4,7 @@ use chrono::{DateTime, Local, Utc};
 use clap::Parser;
 use clio::ClioPath;
 use expanduser::expanduser;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
@@ -11,9 +12,59 @@ use terminal_size::{terminal_size, Height, Width};
 use timediff::TimeDiff;
 use unicode_segmentation::UnicodeSegmentation;
 
+mod git_status;
+use git_status::{GitStatus, GitStatuses};
+
+mod ls_colors;
+use ls_colors::{FileKind, LsColors};
+
 const DEFAULT_WIDTH: usize = 80;
 const DEFAULT_HEIGHT: u16 = 24;
 
+#[derive(clap::ValueEnum, Clone, Copy, PartialEq, Eq, Debug)]
+enum SortKey {
+    Time,
+    Size,
+    Name,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, PartialEq, Eq, Debug)]
+enum ColorMode {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, PartialEq, Eq, Debug)]
+enum OutputFormat {
+    Table,
+    Json,
+}
+
+/// Parses sizes like `10M` or `512` (bytes) into a byte count, for
+/// `--min-size`/`--max-size`.
+fn parse_size(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err("size value cannot be empty".to_string());
+    }
+    let (num_part, unit) = match s.chars().last() {
+        Some(c) if c.is_ascii_alphabetic() => (&s[..s.len() - 1], c.to_ascii_uppercase()),
+        _ => (s, 'B'),
+    };
+    let value: f64 = num_part
+        .parse()
+        .map_err(|_| format!("invalid size value: {}", s))?;
+    let multiplier = match unit {
+        'B' => 1.0,
+        'K' => 1024.0,
+        'M' => 1024.0 * 1024.0,
+        'G' => 1024.0 * 1024.0 * 1024.0,
+        _ => return Err(format!("unknown size suffix in: {}", s)),
+    };
+    Ok((value * multiplier) as u64)
+}
+
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Opts {
@@ -29,6 +80,58 @@ struct Opts {
     /// Show hidden files
     #[clap(short, long)]
     show_hidden: bool,
+
+    /// Recurse into subdirectories
+    #[clap(short = 'R', long)]
+    recursive: bool,
+
+    /// Maximum depth to recurse when --recursive is set
+    #[clap(long)]
+    max_depth: Option<usize>,
+
+    /// Field to sort by
+    #[clap(short = 'S', long, value_enum, default_value = "time")]
+    sort: SortKey,
+
+    /// Reverse the sort order
+    #[clap(long)]
+    reverse: bool,
+
+    /// Only show files at least this size, e.g. `10M`
+    #[clap(long, value_parser = parse_size)]
+    min_size: Option<u64>,
+
+    /// Only show files at most this size, e.g. `1G`
+    #[clap(long, value_parser = parse_size)]
+    max_size: Option<u64>,
+
+    /// Use a long listing format with permissions, owner, and group
+    #[clap(short, long)]
+    long: bool,
+
+    /// Annotate files with their Git working-tree status
+    #[clap(long)]
+    git: bool,
+
+    /// Drop files that Git would ignore (implies Git status is computed)
+    #[clap(long)]
+    git_ignore: bool,
+
+    /// Use last-accessed time instead of modified time
+    #[clap(short = 'u', long, conflicts_with = "created")]
+    accessed: bool,
+
+    /// Use creation time instead of modified time
+    #[clap(short = 'U', long = "created", conflicts_with = "accessed")]
+    created: bool,
+
+    /// Colorize output using LS_COLORS
+    #[clap(long, value_enum, default_value = "auto")]
+    color: ColorMode,
+
+    /// Output format; json suppresses table/ANSI/pager formatting
+    #[clap(long, value_enum, default_value = "table")]
+    format: OutputFormat,
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -40,16 +143,110 @@ enum FileType {
     Dotfile,
 }
 
+/// Which `SystemTime` a listing is keyed on.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum TimeField {
+    Modified,
+    Accessed,
+    Created,
+}
+
+impl TimeField {
+    fn label(&self) -> &'static str {
+        match self {
+            TimeField::Modified => "Modified",
+            TimeField::Accessed => "Accessed",
+            TimeField::Created => "Created",
+        }
+    }
+}
+
 #[derive(Debug)]
 struct File {
     name: String,
-    modified_time: SystemTime,
+    time: SystemTime,
     relative_time: String,
     file_type: FileType,
+    size: u64,
+    mode: u32,
+    uid: u32,
+    gid: u32,
+    git_status: Option<GitStatus>,
     #[allow(dead_code)]
     path: PathBuf,
 }
 
+/// Reads the raw mode bits and owning uid/gid from `metadata`, or zeroes on
+/// platforms without Unix metadata.
+#[cfg(unix)]
+fn unix_ids(metadata: &fs::Metadata) -> (u32, u32, u32) {
+    use std::os::unix::fs::MetadataExt;
+    (metadata.mode(), metadata.uid(), metadata.gid())
+}
+
+#[cfg(not(unix))]
+fn unix_ids(_metadata: &fs::Metadata) -> (u32, u32, u32) {
+    (0, 0, 0)
+}
+
+/// Builds the 10-char `ls -l`-style permission string (e.g. `drwxr-xr-x`)
+/// from a file type and raw Unix mode bits.
+#[cfg(unix)]
+fn permission_string(file_type: &FileType, mode: u32) -> String {
+    let type_char = match file_type {
+        FileType::Directory => 'd',
+        FileType::Symlink => 'l',
+        _ => '-',
+    };
+    let triplet = |shift: u32| {
+        let r = if mode & (0o4 << shift) != 0 { 'r' } else { '-' };
+        let w = if mode & (0o2 << shift) != 0 { 'w' } else { '-' };
+        let x = if mode & (0o1 << shift) != 0 { 'x' } else { '-' };
+        format!("{}{}{}", r, w, x)
+    };
+    format!("{}{}{}{}", type_char, triplet(6), triplet(3), triplet(0))
+}
+
+/// Resolves uid/gid to user/group names through the `users` crate, caching
+/// lookups so a long listing doesn't hit `/etc/passwd` once per file.
+#[cfg(unix)]
+struct IdCache {
+    users: HashMap<u32, String>,
+    groups: HashMap<u32, String>,
+}
+
+#[cfg(unix)]
+impl IdCache {
+    fn new() -> Self {
+        Self {
+            users: HashMap::new(),
+            groups: HashMap::new(),
+        }
+    }
+
+    fn user_name(&mut self, uid: u32) -> String {
+        self.users
+            .entry(uid)
+            .or_insert_with(|| {
+                users::get_user_by_uid(uid)
+                    .map(|u| u.name().to_string_lossy().to_string())
+                    .unwrap_or_else(|| uid.to_string())
+            })
+            .clone()
+    }
+
+    fn group_name(&mut self, gid: u32) -> String {
+        self.groups
+            .entry(gid)
+            .or_insert_with(|| {
+                users::get_group_by_gid(gid)
+                    .map(|g| g.name().to_string_lossy().to_string())
+                    .unwrap_or_else(|| gid.to_string())
+            })
+            .clone()
+    }
+}
+
 fn get_relative_time(t: SystemTime) -> Result<String> {
     let duration = SystemTime::now()
         .duration_since(t)
@@ -76,15 +273,59 @@ fn abbreviate_filename(filename: &str, max_length: usize) -> String {
         filename.to_string()
     }
 }
-fn list_dir(path: &Path, num_files: &usize, show_hidden: bool) -> Result<()> {
+
+/// Formats a byte count like `1.2K`, `3.4M`, `5.6G`, picking the largest
+/// unit for which the value is at least 1.
+fn human_readable_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "K", "M", "G", "T"];
+    let mut size = bytes as f64;
+    let mut unit_idx = 0;
+    while size >= 1024.0 && unit_idx < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit_idx += 1;
+    }
+    if unit_idx == 0 {
+        format!("{}{}", bytes, UNITS[unit_idx])
+    } else {
+        format!("{:.1}{}", size, UNITS[unit_idx])
+    }
+}
+#[allow(clippy::too_many_arguments)]
+fn list_dir(
+    path: &Path,
+    num_files: &usize,
+    show_hidden: bool,
+    recursive: bool,
+    max_depth: Option<usize>,
+    sort: SortKey,
+    reverse: bool,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    long: bool,
+    git: bool,
+    git_ignore: bool,
+    time_field: TimeField,
+    use_color: bool,
+    ls_colors: &LsColors,
+    format: OutputFormat,
+) -> Result<()> {
     let path_str = path.to_str().expect("Unable to convert path to string");
-    let path = expanduser(path_str)?;
-    let raw_entries = path.read_dir().expect("Failed to read directory");
-    let entries = raw_entries.filter_map(|entry| entry.ok());
-    let mut file_info: Vec<File> = entries
-        .filter_map(|entry| get_path_mtime(entry).ok())
-        .collect();
-    // sort by modified time and truncate to the requested number of files
+    let root = expanduser(path_str)?;
+    let mut file_info =
+        collect_entries(&root, &root, show_hidden, recursive, max_depth, time_field);
+    // sort by the requested key and truncate to the requested number of files
+
+    // skip gracefully (no column, no error) when the directory isn't in a repo
+    let git_statuses = if git || git_ignore {
+        GitStatuses::discover(&root)
+    } else {
+        None
+    };
+    if let Some(statuses) = &git_statuses {
+        for file in &mut file_info {
+            file.git_status = Some(statuses.status_for(&file.path));
+        }
+    }
 
     let allowed_types = if show_hidden {
         vec![
@@ -97,49 +338,237 @@ fn list_dir(path: &Path, num_files: &usize, show_hidden: bool) -> Result<()> {
     } else {
         vec![FileType::File, FileType::Directory]
     };
-    file_info.sort_by_key(|f| f.modified_time);
-    file_info.reverse();
+
+    match sort {
+        SortKey::Time => file_info.sort_by_key(|f| f.time),
+        SortKey::Size => file_info.sort_by_key(|f| f.size),
+        SortKey::Name => file_info.sort_by(|a, b| a.name.cmp(&b.name)),
+    }
+    // time/size sort newest/largest first by default; name sorts a-z first
+    let mut descending = sort != SortKey::Name;
+    if reverse {
+        descending = !descending;
+    }
+    if descending {
+        file_info.reverse();
+    }
+
     file_info.retain(|f| {
-        allowed_types.contains(&f.file_type) && (!f.name.starts_with('.') || show_hidden)
+        let basename_hidden = f
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().starts_with('.'))
+            .unwrap_or(false);
+        let size_in_range =
+            min_size.is_none_or(|min| f.size >= min) && max_size.is_none_or(|max| f.size <= max);
+        let not_git_ignored = !git_ignore
+            || !git_statuses
+                .as_ref()
+                .is_some_and(|statuses| statuses.is_ignored(&f.path));
+        allowed_types.contains(&f.file_type)
+            && (!basename_hidden || show_hidden)
+            && size_in_range
+            && not_git_ignored
     });
     file_info.truncate(*num_files);
-    print_file_info(file_info)?;
+    if format == OutputFormat::Json {
+        print_file_info_json(&file_info)?;
+    } else {
+        print_file_info(file_info, long, git, time_field, use_color, ls_colors)?;
+    }
     Ok(())
 }
 
-fn get_path_mtime(entry: fs::DirEntry) -> Result<File> {
-    let path = entry.path();
-    let metadata = fs::metadata(&path);
-    // we we can't get metadata, return an Error
-    let metadata = metadata.context("Unable to get metadata")?;
-    let file_type =
-        if metadata.is_file() && !path.file_name().unwrap().to_string_lossy().starts_with(".") {
-            FileType::File
-        } else if metadata.is_dir() {
-            FileType::Directory
-        } else if metadata.file_type().is_symlink() {
-            FileType::Symlink
-        } else if path.file_name().unwrap().to_string_lossy().starts_with(".") {
-            FileType::Hidden
-        } else {
-            FileType::File
+/// Walks `root` with an explicit work-stack (rather than recursing) so a
+/// deeply nested tree can't blow the call stack, collecting every visited
+/// file into one flat list.
+fn collect_entries(
+    root: &Path,
+    dir: &Path,
+    show_hidden: bool,
+    recursive: bool,
+    max_depth: Option<usize>,
+    time_field: TimeField,
+) -> Vec<File> {
+    let mut file_info = Vec::new();
+    let mut stack: Vec<(PathBuf, usize)> = vec![(dir.to_path_buf(), 0)];
+
+    while let Some((current_dir, depth)) = stack.pop() {
+        let raw_entries = match current_dir.read_dir() {
+            Ok(entries) => entries,
+            Err(e) => {
+                eprintln!(
+                    "Warning: unable to read directory {}: {}",
+                    current_dir.display(),
+                    e
+                );
+                continue;
+            }
         };
 
-    let modified_time = metadata.modified().expect("Unable to get modified time");
+        for entry in raw_entries.filter_map(|entry| entry.ok()) {
+            let entry_path = entry.path();
+            let is_hidden = entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with('.');
+            // entry.file_type() does not follow symlinks, unlike entry_path.is_dir();
+            // descending into a symlinked directory can walk back into an ancestor
+            // and recurse forever
+            let is_real_dir = entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false);
+
+            if recursive
+                && is_real_dir
+                && (show_hidden || !is_hidden)
+                && max_depth.is_none_or(|max| depth < max)
+            {
+                stack.push((entry_path, depth + 1));
+            }
+
+            match get_path_time(entry, root, time_field) {
+                Ok(file) => file_info.push(file),
+                Err(e) => eprintln!("Warning: {}", e),
+            }
+        }
+    }
 
-    let relative_time = get_relative_time(modified_time).expect("Unable to get relative time");
-    let name = path.file_name().unwrap().to_string_lossy().to_string();
+    file_info
+}
+
+fn get_path_time(entry: fs::DirEntry, root: &Path, time_field: TimeField) -> Result<File> {
+    let path = entry.path();
+    // symlink_metadata does not follow the link, so it's the only way to tell
+    // a symlink from its target; fs::metadata would report the target's type
+    let symlink_metadata = fs::symlink_metadata(&path).context("Unable to get metadata")?;
+    let is_symlink = symlink_metadata.file_type().is_symlink();
+
+    // follow the link for size/time/permissions, falling back to the link's
+    // own metadata (e.g. a broken symlink) rather than erroring out
+    let metadata = if is_symlink {
+        fs::metadata(&path).unwrap_or_else(|_| symlink_metadata.clone())
+    } else {
+        symlink_metadata
+    };
+
+    let file_type = if is_symlink {
+        FileType::Symlink
+    } else if metadata.is_file() && !path.file_name().unwrap().to_string_lossy().starts_with(".") {
+        FileType::File
+    } else if metadata.is_dir() {
+        FileType::Directory
+    } else if path.file_name().unwrap().to_string_lossy().starts_with(".") {
+        FileType::Hidden
+    } else {
+        FileType::File
+    };
+
+    let time = match time_field {
+        TimeField::Modified => metadata.modified().context("Unable to get modified time")?,
+        TimeField::Accessed => metadata.accessed().context("Unable to get accessed time")?,
+        TimeField::Created => metadata
+            .created()
+            .context("Unable to get created time: not supported on this platform/filesystem")?,
+    };
+
+    let relative_time = get_relative_time(time).expect("Unable to get relative time");
+    // relative to the root so files with the same basename in different
+    // subdirectories remain distinguishable
+    let name = path
+        .strip_prefix(root)
+        .unwrap_or(&path)
+        .to_string_lossy()
+        .to_string();
+    let size = metadata.len();
+    let (mode, uid, gid) = unix_ids(&metadata);
     Ok(File {
         name,
-        modified_time,
+        time,
         relative_time,
         file_type,
+        size,
+        mode,
+        uid,
+        gid,
+        git_status: None,
         path,
     })
 }
 
-fn print_file_info(file_info: Vec<File>) -> Result<()> {
-    let is_tty = atty::is(Stream::Stdout);
+/// Determines a file's `LS_COLORS` type code: directory, symlink, or
+/// executable (by Unix mode bits) take priority over an extension match.
+fn file_kind(file: &File) -> FileKind {
+    if file.file_type == FileType::Directory {
+        FileKind::Directory
+    } else if file.file_type == FileType::Symlink {
+        FileKind::Symlink
+    } else if file.mode & 0o111 != 0 {
+        FileKind::Executable
+    } else {
+        FileKind::Regular
+    }
+}
+
+/// The shape of a `--format json` entry: independent of `File`'s internal
+/// fields so the wire format doesn't shift when those change.
+#[derive(serde::Serialize)]
+struct JsonFile {
+    name: String,
+    path: String,
+    time: String,
+    time_iso8601: String,
+    relative_time: String,
+    size: u64,
+    file_type: String,
+}
+
+impl From<&File> for JsonFile {
+    fn from(file: &File) -> Self {
+        let absolute_path = file
+            .path
+            .canonicalize()
+            .unwrap_or_else(|_| file.path.clone());
+        let datetime: DateTime<Utc> = DateTime::<Utc>::from(file.time);
+        let file_type = match file.file_type {
+            FileType::File => "file",
+            FileType::Directory => "directory",
+            FileType::Symlink => "symlink",
+            FileType::Hidden => "hidden",
+            FileType::Dotfile => "dotfile",
+        };
+        JsonFile {
+            name: file.name.clone(),
+            path: absolute_path.to_string_lossy().to_string(),
+            time: human_readable_system_time(file.time),
+            time_iso8601: datetime.to_rfc3339(),
+            relative_time: file.relative_time.clone(),
+            size: file.size,
+            file_type: file_type.to_string(),
+        }
+    }
+}
+
+/// `--format json` output: a clean, parseable array with no ANSI codes.
+fn print_file_info_json(file_info: &[File]) -> Result<()> {
+    let json_files: Vec<JsonFile> = file_info.iter().map(JsonFile::from).collect();
+    println!("{}", serde_json::to_string(&json_files)?);
+    Ok(())
+}
+
+fn print_file_info(
+    file_info: Vec<File>,
+    long: bool,
+    git: bool,
+    time_field: TimeField,
+    use_color: bool,
+    ls_colors: &LsColors,
+) -> Result<()> {
+    if long {
+        #[cfg(unix)]
+        return print_file_info_long(file_info, git, time_field, use_color, ls_colors);
+
+        #[cfg(not(unix))]
+        eprintln!("Warning: -l/--long is not supported on this platform; showing normal output");
+    }
 
     // Get terminal width or use default if not available
     let term_width = if let Some((Width(w), _)) = terminal_size() {
@@ -148,76 +577,133 @@ fn print_file_info(file_info: Vec<File>) -> Result<()> {
         DEFAULT_WIDTH
     };
 
-    let total_spacing = 4;
+    let git_width = if git { 3 } else { 0 };
+    let total_spacing = 6 + git_width;
     let available_width = term_width.saturating_sub(total_spacing);
 
-    let name_width = (available_width * 5) / 10;
+    let name_width = (available_width * 4) / 10;
     let modified_time_width = (available_width * 3) / 10;
-    let relative_time_width = available_width - name_width - modified_time_width;
+    let size_width = available_width / 10;
+    let relative_time_width = available_width - name_width - modified_time_width - size_width;
 
     let name_width = name_width.max(20);
     let modified_time_width = modified_time_width.max(15);
+    let size_width = size_width.max(6);
     let relative_time_width = relative_time_width.max(10);
 
+    let git_header = if git { "St " } else { "" };
+    let time_header = format!("{} Time", time_field.label());
+
     // Show table headers
-    if is_tty {
-        println!(
-            "\x1b[1m{:<name_width$}  {:<modified_time_width$}  {:<relative_time_width$}\x1b[0m",
-            "Name",
-            "Modified Time",
-            "Relative Time",
-            name_width = name_width,
-            modified_time_width = modified_time_width,
-            relative_time_width = relative_time_width
-        );
+    let header_line = format!(
+        "{}{:<name_width$}  {:<modified_time_width$}  {:<size_width$}  {:<relative_time_width$}",
+        git_header,
+        "Name",
+        time_header,
+        "Size",
+        "Relative Time",
+        name_width = name_width,
+        modified_time_width = modified_time_width,
+        size_width = size_width,
+        relative_time_width = relative_time_width
+    );
+    if use_color {
+        println!("\x1b[1m{}\x1b[0m", header_line);
     } else {
-        println!(
-            "{:<name_width$}  {:<modified_time_width$}  {:<relative_time_width$}",
-            "Name",
-            "Modified Time",
-            "Relative Time",
-            name_width = name_width,
-            modified_time_width = modified_time_width,
-            relative_time_width = relative_time_width
-        );
+        println!("{}", header_line);
     }
 
     for file in file_info {
-        let hr_time = human_readable_system_time(file.modified_time);
+        let hr_time = human_readable_system_time(file.time);
+        let hr_size = human_readable_size(file.size);
         let filename_abbreviated = abbreviate_filename(&file.name, name_width);
-        if file.file_type == FileType::Directory {
-            if is_tty {
-                println!(
-                    "\x1b[34m{:<width$}\x1b[0m  {:<modified_width$}  {:<relative_width$}",
-                    filename_abbreviated,
-                    hr_time,
-                    file.relative_time,
-                    width = name_width,
-                    modified_width = modified_time_width,
-                    relative_width = relative_time_width
-                );
+        let git_col = if git {
+            let status = file.git_status.unwrap_or(GitStatus::Clean);
+            let flag = if use_color {
+                status.colored_flag()
             } else {
-                println!(
-                    "{:<width$}  {:<modified_width$}  {:<relative_width$}",
-                    filename_abbreviated,
-                    hr_time,
-                    file.relative_time,
-                    width = name_width,
-                    modified_width = modified_time_width,
-                    relative_width = relative_time_width
-                );
+                status.flag()
+            };
+            format!("{} ", flag)
+        } else {
+            String::new()
+        };
+        let (prefix, suffix) = if use_color {
+            match ls_colors.style_for(file_kind(&file), &file.name) {
+                Some(style) => (format!("\x1b[{}m", style), "\x1b[0m"),
+                None => (String::new(), ""),
             }
         } else {
-            println!(
-                "{:<width$}  {:<modified_width$}  {:<relative_width$}",
-                filename_abbreviated,
-                hr_time,
-                file.relative_time,
-                width = name_width,
-                modified_width = modified_time_width,
-                relative_width = relative_time_width
-            );
-        }
+            (String::new(), "")
+        };
+        println!(
+            "{}{}{:<width$}{}  {:<modified_width$}  {:<size_width$}  {:<relative_width$}",
+            git_col,
+            prefix,
+            filename_abbreviated,
+            suffix,
+            hr_time,
+            hr_size,
+            file.relative_time,
+            width = name_width,
+            modified_width = modified_time_width,
+            size_width = size_width,
+            relative_width = relative_time_width
+        );
+    }
+    Ok(())
+}
+
+/// `-l/--long` output: permissions, owner, group, size, the selected time
+/// field (modified, accessed or created), name.
+#[cfg(unix)]
+fn print_file_info_long(
+    file_info: Vec<File>,
+    git: bool,
+    time_field: TimeField,
+    use_color: bool,
+    ls_colors: &LsColors,
+) -> Result<()> {
+    let mut ids = IdCache::new();
+    let git_header = if git { "St " } else { "" };
+    let time_header = format!("{} Time", time_field.label());
+
+    let header_line = format!(
+        "{}{:<10}  {:<8}  {:<8}  {:>8}  {:<19}  Name",
+        git_header, "Mode", "Owner", "Group", "Size", time_header
+    );
+    if use_color {
+        println!("\x1b[1m{}\x1b[0m", header_line);
+    } else {
+        println!("{}", header_line);
+    }
+
+    for file in file_info {
+        let perms = permission_string(&file.file_type, file.mode);
+        let owner = ids.user_name(file.uid);
+        let group = ids.group_name(file.gid);
+        let hr_size = human_readable_size(file.size);
+        let hr_time = human_readable_system_time(file.time);
+        let git_col = if git {
+            let status = file.git_status.unwrap_or(GitStatus::Clean);
+            let flag = if use_color {
+                status.colored_flag()
+            } else {
+                status.flag()
+            };
+            format!("{} ", flag)
+        } else {
+            String::new()
+        };
+        let name = if use_color {
+            ls_colors.paint(file_kind(&file), &file.name, &file.name)
+        } else {
+            file.name.clone()
+        };
+        println!(
+            "{}{:<10}  {:<8}  {:<8}  {:>8}  {:<19}  {}",
+            git_col, perms, owner, group, hr_size, hr_time, name
+        );
     }
     Ok(())
 }
@@ -231,12 +717,45 @@ fn main() -> Result<()> {
     };
     // use a pager if the number of files exceeds the terminal height
     let display_height = opts.num_files - 1;
-    if display_height > term_height as usize {
+    if opts.format != OutputFormat::Json && display_height > term_height as usize {
         let mut pager = pager::Pager::new();
         pager.setup();
     }
 
-    let res = list_dir(&opts.directory, &opts.num_files, opts.show_hidden);
+    let time_field = if opts.accessed {
+        TimeField::Accessed
+    } else if opts.created {
+        TimeField::Created
+    } else {
+        TimeField::Modified
+    };
+
+    let use_color = opts.format != OutputFormat::Json
+        && match opts.color {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => atty::is(Stream::Stdout),
+        };
+    let ls_colors = LsColors::from_env();
+
+    let res = list_dir(
+        &opts.directory,
+        &opts.num_files,
+        opts.show_hidden,
+        opts.recursive,
+        opts.max_depth,
+        opts.sort,
+        opts.reverse,
+        opts.min_size,
+        opts.max_size,
+        opts.long,
+        opts.git,
+        opts.git_ignore,
+        time_field,
+        use_color,
+        &ls_colors,
+        opts.format,
+    );
     match res {
         Ok(_) => Ok(()),
         Err(e) => {
@@ -245,3 +764,50 @@ fn main() -> Result<()> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_size_defaults_to_bytes() {
+        assert_eq!(parse_size("512").unwrap(), 512);
+    }
+
+    #[test]
+    fn parse_size_understands_suffixes() {
+        assert_eq!(parse_size("1K").unwrap(), 1024);
+        assert_eq!(parse_size("1M").unwrap(), 1024 * 1024);
+        assert_eq!(parse_size("1G").unwrap(), 1024 * 1024 * 1024);
+    }
+
+    #[test]
+    fn parse_size_is_case_insensitive_and_allows_fractions() {
+        assert_eq!(parse_size("1.5k").unwrap(), 1536);
+    }
+
+    #[test]
+    fn parse_size_rejects_empty_and_invalid_input() {
+        assert!(parse_size("").is_err());
+        assert!(parse_size("10X").is_err());
+        assert!(parse_size("abc").is_err());
+    }
+
+    #[test]
+    fn human_readable_size_stays_in_bytes_below_1024() {
+        assert_eq!(human_readable_size(0), "0B");
+        assert_eq!(human_readable_size(1023), "1023B");
+    }
+
+    #[test]
+    fn human_readable_size_crosses_unit_boundaries() {
+        assert_eq!(human_readable_size(1024), "1.0K");
+        assert_eq!(human_readable_size(1024 * 1024), "1.0M");
+        assert_eq!(human_readable_size(1024 * 1024 * 1024), "1.0G");
+    }
+
+    #[test]
+    fn human_readable_size_caps_at_terabytes() {
+        assert_eq!(human_readable_size(1024u64.pow(5)), "1024.0T");
+    }
+}